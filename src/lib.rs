@@ -49,6 +49,7 @@
 //!     pub raw_uri: String,
 //!     pub queries: HashMap<String, String>,
 //!     pub params: HashMap<String, String>,
+//!     pub param_langs: HashMap<String, String>,
 //! }
 //!
 //! type Rel = String;
@@ -79,6 +80,56 @@
 //! assert_eq!(val.get("last").unwrap().raw_uri, "https://api.github.com/repositories/41986369/contributors?page=14");
 //! ```
 //!
+//! ## format_link_header
+//!
+//! > Version >= 0.4.0
+//!
+//! Use `format_link_header()` (or the `Display` impl on [`Link`](struct.Link.html)) to go the
+//! other direction and turn a [`LinkMap`](type.LinkMap.html) back into a `Link:` header value:
+//!
+//! ```rust
+//! let link_header = r#"<https://api.github.com/repositories/41986369/contributors?page=2>; rel="next""#;
+//!
+//! let map = parse_link_header::parse(link_header).unwrap();
+//! let formatted = parse_link_header::format_link_header(&map);
+//!
+//! assert_eq!(parse_link_header::parse(&formatted).unwrap(), map);
+//! ```
+//!
+//! ## parse_with_opts
+//!
+//! > Version >= 0.4.0
+//!
+//! By default, query and parameter values are kept exactly as they appeared in the header (e.g. a
+//! `cursor` value stays percent-encoded). Use `parse_with_opts()` with [`ParseOptions`] to have
+//! them percent-decoded instead. RFC 8187 extended (`name*`) parameters are decoded either way:
+//!
+//! ```rust
+//! use parse_link_header::ParseOptions;
+//!
+//! let link_header = r#"<https://example.com/search?q=rust%20lang>; rel="next""#;
+//!
+//! let opts = ParseOptions { decode_queries: true, decode_params: false };
+//! let val = parse_link_header::parse_with_opts(link_header, &opts).unwrap();
+//!
+//! assert_eq!(val.get(&Some("next".to_string())).unwrap().queries.get("q").unwrap(), "rust lang");
+//! ```
+//!
+//! ## parse_multi
+//!
+//! > Version >= 0.4.0
+//!
+//! A `Link:` header can legally carry several entries with the same `rel` (e.g. two
+//! `rel="alternate"` entries with different `type`s). `parse()` only keeps the last one it sees;
+//! use `parse_multi()` (or `parse_with_rel_multi()`) to get every entry, in header order:
+//!
+//! ```rust
+//! let link_header = r#"<https://example.com/feed.json>; rel="alternate"; type="application/json", <https://example.com/feed.xml>; rel="alternate"; type="application/xml""#;
+//!
+//! let val = parse_link_header::parse_multi(link_header).unwrap();
+//! assert_eq!(val.get(&Some("alternate".to_string())).unwrap().len(), 2);
+//! ```
+//!
 //! ## Feature: `url`
 //!
 //! > Version >= 0.3.0
@@ -168,7 +219,89 @@ pub struct Link {
     /// A `HashMap` of the parameters associated with this URI.  The most common is `rel`,
     /// indicating the relationship between the current HTTP data being fetched and the URI in this
     /// `Link:` header.
+    ///
+    /// Extended parameters per [RFC 8187](https://tools.ietf.org/html/rfc8187) (e.g. `title*`) are
+    /// percent-decoded and stored here under their base name (the trailing `*` is stripped); their
+    /// language tag, if any, ends up in [`param_langs`](#structfield.param_langs).
     pub params: HashMap<String, String>,
+
+    /// A `HashMap` recording the language tag of any extended (`name*`) parameter that specified
+    /// one, keyed by the same base name used in [`params`](#structfield.params).
+    pub param_langs: HashMap<String, String>,
+}
+
+impl fmt::Display for Link {
+    /// Formats this entry the way it would appear inside a `Link:` header, e.g.
+    /// `<https://example.com>; rel="next"; title="Page 2"`.
+    ///
+    /// Parameters are quoted (with `\` and `"` escaped), `rel` is emitted first for
+    /// readability, and the rest follow in sorted order so output is deterministic. A
+    /// parameter with an entry in `param_langs` is re-encoded as an RFC 8187 extended
+    /// parameter (`name*=UTF-8'lang'pct-value`) instead of a plain quoted one.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.raw_uri)?;
+
+        if let Some(rel) = self.params.get("rel") {
+            write!(f, "; rel={}", quote_param_value(rel))?;
+        }
+
+        let mut keys: Vec<&String> = self.params.keys().filter(|k| k.as_str() != "rel").collect();
+        keys.sort();
+
+        for key in keys {
+            let value = &self.params[key];
+
+            match self.param_langs.get(key) {
+                Some(lang) => write!(f, "; {}*=UTF-8'{}'{}", key, lang, percent_encode_ext(value))?,
+                None => write!(f, "; {}={}", key, quote_param_value(value))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Wraps a parameter value in double quotes, escaping any `\` or `"` it contains.
+fn quote_param_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+
+    for c in value.chars() {
+        if c == '\\' || c == '"' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+
+    out.push('"');
+    out
+}
+
+/// Percent-encodes a string for use as the value of an RFC 8187 extended parameter,
+/// leaving only alphanumerics and `-._~` (a subset of the RFC 5987 `attr-char` set) untouched.
+fn percent_encode_ext(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for b in value.as_bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(*b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+
+    out
+}
+
+/// Serializes every entry in a [`LinkMap`](type.LinkMap.html) back into a single `Link:`
+/// header value, joining entries with `, `. Feeding the result back through [`parse`]
+/// reproduces the original map.
+pub fn format_link_header(map: &LinkMap) -> String {
+    map.values()
+        .map(|link| link.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 type Rel = String;
@@ -189,7 +322,9 @@ pub type RelLinkMap = HashMap<Rel, Link>;
 /// Takes a `&str` which is the value of the HTTP `Link:` header, attempts to parse it, and returns
 /// a `Result<RelLinkMap>` which represents the mapping between the relationship and the link entry.
 pub fn parse_with_rel(link_header: &str) -> Result<RelLinkMap> {
-    parse_with(link_header, |x| x.ok_or(Error(ErrorKind::MissingRel)))
+    parse_with(link_header, None, ParseOptions::default(), |x| {
+        x.ok_or(Error(ErrorKind::MissingRel))
+    })
 }
 
 /// Parse link header into a [`LinkMap`](type.LinkMap.html).
@@ -197,87 +332,567 @@ pub fn parse_with_rel(link_header: &str) -> Result<RelLinkMap> {
 /// Takes a `&str` which is the value of the HTTP `Link:` header, attempts to parse it, and returns
 /// a `Result<LinkMap>` which represents the mapping between the relationship and the link entry.
 pub fn parse(link_header: &str) -> Result<LinkMap> {
-    parse_with(link_header, Ok)
+    parse_with(link_header, None, ParseOptions::default(), Ok)
 }
 
-/// Generic parser function
+/// Parse link header into a [`LinkMap`](type.LinkMap.html), resolving any relative-reference
+/// targets (e.g. `</foo/bar>`) against `base` per RFC 3986 section 5.
 ///
-/// Does the actual parsing work, and then uses make_key() to proceses the HashMap key into the
-/// desired type.
-fn parse_with<K, F>(link_header: &str, make_key: F) -> Result<HashMap<K, Link>>
-where
-    K: Eq + std::hash::Hash,
-    F: Fn(Option<String>) -> Result<K>,
-{
-    use lazy_static::lazy_static;
-    use regex::Regex;
+/// With the default `http::Uri` backend this merges paths and removes dot-segments itself; with
+/// the `url` feature enabled it delegates to `url::Url::join`. `Link::raw_uri` keeps the original,
+/// unresolved text; `Link::uri` holds the resolved, absolute form.
+pub fn parse_with_base(link_header: &str, base: &str) -> Result<LinkMap> {
+    parse_with(link_header, Some(base), ParseOptions::default(), Ok)
+}
 
-    lazy_static! {
-        static ref RE: Result<Regex> =
-            Regex::new(r#"[<>"\s]"#).or(Err(Error(ErrorKind::InternalError)));
-    }
-    let mut result = HashMap::new();
+/// Options controlling how far [`parse_with_opts`] decodes the values it collects.
+///
+/// Both options default to `false`, matching the raw, undecoded behavior of [`parse`] -- callers
+/// that need the original percent-encoded bytes (rather than decoded text) can keep relying on
+/// that default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Percent-decode the values collected in [`Link::queries`].
+    pub decode_queries: bool,
+
+    /// Percent-decode plain (non-`name*`) parameter values collected in [`Link::params`], quoted
+    /// or not.
+    pub decode_params: bool,
+}
 
-    // remove all quotes, angle brackets, and whitespace
-    let preprocessed = RE.as_ref()?.replace_all(link_header, "");
+/// Parse link header into a [`LinkMap`](type.LinkMap.html), percent-decoding query and/or
+/// parameter values as directed by `opts`. `Link::raw_uri` is never affected.
+pub fn parse_with_opts(link_header: &str, opts: &ParseOptions) -> Result<LinkMap> {
+    parse_with(link_header, None, *opts, Ok)
+}
 
-    // split along comma into different entries
-    let splited = preprocessed.split(',');
+/// States used by [`tokenize`] while walking a `Link:` header one character at a time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TokenState {
+    BeforeUri,
+    InUri,
+    BeforeParamKey,
+    InKey,
+    BeforeValue,
+    InQuotedValue,
+    InTokenValue,
+}
 
-    for s in splited {
-        // split each entry into parts
-        let mut link_vec: Vec<_> = s.split(';').collect();
-        link_vec.reverse();
+/// A raw URI paired with its (still-undecoded) parameter list, as produced by [`tokenize`].
+type TokenizedEntry = (String, Vec<(String, String)>);
 
-        // pop off the link value; the split() guarantees at least one entry to pop()
-        let raw_uri = link_vec
-            .pop()
-            .ok_or(Error(ErrorKind::InternalError))?
-            .to_string();
-        let uri: Uri = raw_uri.parse().or(Err(Error(ErrorKind::InvalidURI)))?;
+/// Splits a `Link:` header into `(raw_uri, params)` pairs without the lossy
+/// strip-everything-then-split approach `parse_with` used to rely on.
+///
+/// Quoted parameter values are tracked with a dedicated state so that a comma or
+/// semicolon inside a quoted string (e.g. `title="Page 2, final"`) is treated as
+/// literal text rather than an entry or parameter separator. A `\"` inside a quoted
+/// value is treated as an escaped quote. A target missing its closing `>`, or a
+/// parameter value with an unterminated quote, is reported as `MalformedParam`.
+fn tokenize(link_header: &str) -> Result<Vec<TokenizedEntry>> {
+    use TokenState::*;
+
+    let mut entries = Vec::new();
+    let mut state = BeforeUri;
+
+    let mut uri = String::new();
+    let mut key = String::new();
+    let mut value = String::new();
+    let mut params: Vec<(String, String)> = Vec::new();
+    let mut escaped = false;
+
+    macro_rules! push_param {
+        () => {
+            if !key.trim().is_empty() {
+                params.push((key.trim().to_string(), std::mem::take(&mut value)));
+            }
+            key.clear();
+            value.clear();
+        };
+    }
 
-        let mut queries = HashMap::new();
-        if let Some(query) = uri.query() {
-            let mut query = query.to_string();
+    macro_rules! push_entry {
+        () => {
+            entries.push((std::mem::take(&mut uri), std::mem::take(&mut params)));
+        };
+    }
 
-            // skip leading ampersand
-            if query.starts_with('&') {
-                query = query.chars().skip(1).collect();
+    for c in link_header.chars() {
+        match state {
+            BeforeUri => match c {
+                '<' => state = InUri,
+                c if c.is_whitespace() || c == ',' => {}
+                _ => return Err(Error(ErrorKind::MalformedParam)),
+            },
+            InUri => match c {
+                '>' => state = BeforeParamKey,
+                _ => uri.push(c),
+            },
+            BeforeParamKey => match c {
+                ';' => {}
+                ',' => {
+                    push_entry!();
+                    state = BeforeUri;
+                }
+                c if c.is_whitespace() => {}
+                _ => {
+                    key.push(c);
+                    state = InKey;
+                }
+            },
+            InKey => match c {
+                '=' => state = BeforeValue,
+                ';' | ',' => return Err(Error(ErrorKind::MalformedParam)),
+                _ => key.push(c),
+            },
+            BeforeValue => match c {
+                '"' => state = InQuotedValue,
+                ';' => {
+                    push_param!();
+                    state = BeforeParamKey;
+                }
+                ',' => {
+                    push_param!();
+                    push_entry!();
+                    state = BeforeUri;
+                }
+                c if c.is_whitespace() => {}
+                _ => {
+                    value.push(c);
+                    state = InTokenValue;
+                }
+            },
+            InQuotedValue => {
+                if escaped {
+                    value.push(c);
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    push_param!();
+                    state = BeforeParamKey;
+                } else {
+                    value.push(c);
+                }
             }
+            InTokenValue => match c {
+                ';' => {
+                    value = value.trim_end().to_string();
+                    push_param!();
+                    state = BeforeParamKey;
+                }
+                ',' => {
+                    value = value.trim_end().to_string();
+                    push_param!();
+                    push_entry!();
+                    state = BeforeUri;
+                }
+                _ => value.push(c),
+            },
+        }
+    }
 
-            // split each query and extract as (key, value) pairs
-            for q in query.split('&') {
-                let (key, val) = q.split_once('=').ok_or(Error(ErrorKind::MalformedQuery))?;
+    match state {
+        InUri | InQuotedValue => return Err(Error(ErrorKind::MalformedParam)),
+        InKey if !key.trim().is_empty() => return Err(Error(ErrorKind::MalformedParam)),
+        InTokenValue => {
+            value = value.trim_end().to_string();
+            push_param!();
+            push_entry!();
+        }
+        BeforeValue | InKey => {
+            push_param!();
+            push_entry!();
+        }
+        BeforeParamKey => {
+            push_entry!();
+        }
+        BeforeUri => {}
+    }
+
+    Ok(entries)
+}
 
-                queries.insert(key.to_string(), val.to_string());
+/// Percent-decodes a string per RFC 3986 section 2.1, returning the raw decoded bytes.
+///
+/// A `%` not followed by two hex digits is reported as `err_kind`.
+fn percent_decode(s: &str, err_kind: ErrorKind) -> Result<Vec<u8>> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).ok_or(Error(err_kind))?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return Err(Error(err_kind));
             }
+            let hex = std::str::from_utf8(hex).or(Err(Error(err_kind)))?;
+            let byte = u8::from_str_radix(hex, 16).or(Err(Error(err_kind)))?;
+
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
         }
+    }
+
+    Ok(out)
+}
+
+/// Percent-decodes a string and validates the result as UTF-8, reporting either failure as
+/// `err_kind`.
+fn decode_percent_str(s: &str, err_kind: ErrorKind) -> Result<String> {
+    let decoded = percent_decode(s, err_kind)?;
+
+    String::from_utf8(decoded).or(Err(Error(err_kind)))
+}
+
+/// Decodes the value of an RFC 8187 extended parameter (`name*=charset'lang'pct-value`) into
+/// its `(charset, language, value)` parts, percent-decoding the value along the way.
+///
+/// Only the `UTF-8` charset is supported, since that covers every extended parameter seen in
+/// practice; anything else is reported as `MalformedParam`.
+fn decode_ext_value(raw: &str) -> Result<(String, String, String)> {
+    let mut parts = raw.splitn(3, '\'');
+
+    let charset = parts.next().ok_or(Error(ErrorKind::MalformedParam))?;
+    let lang = parts.next().ok_or(Error(ErrorKind::MalformedParam))?;
+    let value = parts.next().ok_or(Error(ErrorKind::MalformedParam))?;
+
+    if !charset.eq_ignore_ascii_case("utf-8") {
+        return Err(Error(ErrorKind::MalformedParam));
+    }
+
+    let value = decode_percent_str(value, ErrorKind::MalformedParam)?;
 
-        let mut params = HashMap::new();
+    Ok((charset.to_string(), lang.to_string(), value))
+}
+
+/// Resolves `raw_uri` against `base`, per RFC 3986 section 5.
+#[cfg(feature = "url")]
+fn resolve_reference(raw_uri: &str, base: &str) -> Result<Uri> {
+    let base = Uri::parse(base).or(Err(Error(ErrorKind::InvalidURI)))?;
+
+    base.join(raw_uri).or(Err(Error(ErrorKind::InvalidURI)))
+}
 
-        // extract the parameter list as (key, value) pairs
-        for param in link_vec {
-            let (key, val) = param
-                .split_once('=')
-                .ok_or(Error(ErrorKind::MalformedParam))?;
+/// The pieces of a reference (a possibly-relative target) as split by [`split_reference`],
+/// before any merging against a base has happened.
+#[cfg(not(feature = "url"))]
+struct Reference<'a> {
+    has_scheme: bool,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+}
 
-            params.insert(key.to_string(), val.to_string());
+/// Splits a reference into scheme presence, authority, path and query, by hand rather than
+/// through `http::Uri`'s `FromStr` parser -- that parser only accepts origin-form (leading `/`),
+/// absolute-form, authority-form or `*`, so it rejects the relative-refs (`../sibling`,
+/// `foo/bar`) this function exists to handle.
+#[cfg(not(feature = "url"))]
+fn split_reference(s: &str) -> Reference<'_> {
+    let s = match s.find('#') {
+        Some(idx) => &s[..idx],
+        None => s,
+    };
+
+    let scheme_end = s.find(':').filter(|&idx| {
+        idx > 0
+            && s.as_bytes()[0].is_ascii_alphabetic()
+            && s[..idx]
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+    });
+
+    let rest = match scheme_end {
+        Some(idx) => &s[idx + 1..],
+        None => s,
+    };
+
+    let (authority, rest) = match rest.strip_prefix("//") {
+        Some(rest) => {
+            let end = rest.find(['/', '?']).unwrap_or(rest.len());
+            (Some(&rest[..end]), &rest[end..])
         }
+        None => (None, rest),
+    };
+
+    let (path, query) = match rest.find('?') {
+        Some(idx) => (&rest[..idx], Some(&rest[idx + 1..])),
+        None => (rest, None),
+    };
+
+    Reference {
+        has_scheme: scheme_end.is_some(),
+        authority,
+        path,
+        query,
+    }
+}
 
-        result.insert(
-            make_key(params.get("rel").cloned())?,
-            Link {
-                uri,
-                raw_uri,
-                queries,
-                params,
-            },
-        );
+/// Resolves `raw_uri` against `base`, per RFC 3986 section 5: an already-absolute `raw_uri`
+/// (one with a scheme) is returned untouched, otherwise its path is merged onto `base`'s and
+/// dot-segments are removed.
+#[cfg(not(feature = "url"))]
+fn resolve_reference(raw_uri: &str, base: &str) -> Result<Uri> {
+    let target = split_reference(raw_uri);
+
+    if target.has_scheme {
+        return raw_uri.parse().or(Err(Error(ErrorKind::InvalidURI)));
+    }
+
+    let base: Uri = base.parse().or(Err(Error(ErrorKind::InvalidURI)))?;
+    let scheme = base.scheme().ok_or(Error(ErrorKind::InvalidURI))?.clone();
+
+    let (authority, path, query) = if let Some(authority) = target.authority {
+        (
+            Some(authority.to_string()),
+            remove_dot_segments(target.path),
+            target.query.map(str::to_string),
+        )
+    } else if target.path.is_empty() {
+        (
+            base.authority().map(ToString::to_string),
+            base.path().to_string(),
+            target
+                .query
+                .map(str::to_string)
+                .or_else(|| base.query().map(str::to_string)),
+        )
+    } else if target.path.starts_with('/') {
+        (
+            base.authority().map(ToString::to_string),
+            remove_dot_segments(target.path),
+            target.query.map(str::to_string),
+        )
+    } else {
+        (
+            base.authority().map(ToString::to_string),
+            remove_dot_segments(&merge_paths(&base, target.path)),
+            target.query.map(str::to_string),
+        )
+    };
+
+    let path_and_query = match query {
+        Some(query) => format!("{}?{}", path, query),
+        None => path,
+    };
+
+    let mut builder = http::Uri::builder().scheme(scheme);
+
+    if let Some(authority) = authority {
+        builder = builder.authority(authority);
+    }
+
+    builder = builder.path_and_query(path_and_query);
+
+    builder.build().or(Err(Error(ErrorKind::InvalidURI)))
+}
+
+/// Merges a relative path onto `base`'s path, per RFC 3986 section 5.3.
+#[cfg(not(feature = "url"))]
+fn merge_paths(base: &Uri, ref_path: &str) -> String {
+    if base.authority().is_some() && base.path().is_empty() {
+        format!("/{}", ref_path)
+    } else {
+        match base.path().rfind('/') {
+            Some(idx) => format!("{}{}", &base.path()[..=idx], ref_path),
+            None => ref_path.to_string(),
+        }
+    }
+}
+
+/// Removes `.` and `..` segments from a URI path, per RFC 3986 section 5.2.4.
+#[cfg(not(feature = "url"))]
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path.to_string();
+    let mut output = String::new();
+
+    while !input.is_empty() {
+        if let Some(rest) = input.strip_prefix("../") {
+            input = rest.to_string();
+        } else if let Some(rest) = input.strip_prefix("./") {
+            input = rest.to_string();
+        } else if let Some(rest) = input.strip_prefix("/./") {
+            input = format!("/{}", rest);
+        } else if input == "/." {
+            input = "/".to_string();
+        } else if let Some(rest) = input.strip_prefix("/../") {
+            input = format!("/{}", rest);
+            truncate_last_segment(&mut output);
+        } else if input == "/.." {
+            input = "/".to_string();
+            truncate_last_segment(&mut output);
+        } else if input == "." || input == ".." {
+            input = String::new();
+        } else {
+            let first_slash = if let Some(rest) = input.strip_prefix('/') {
+                rest.find('/').map(|i| i + 1).unwrap_or(input.len())
+            } else {
+                input.find('/').unwrap_or(input.len())
+            };
+
+            output.push_str(&input[..first_slash]);
+            input = input[first_slash..].to_string();
+        }
+    }
+
+    output
+}
+
+/// Drops the last `/`-delimited segment from `output`, used by [`remove_dot_segments`] when it
+/// encounters a `..` segment.
+#[cfg(not(feature = "url"))]
+fn truncate_last_segment(output: &mut String) {
+    match output.rfind('/') {
+        Some(idx) => output.truncate(idx),
+        None => output.clear(),
+    }
+}
+
+/// Builds a single [`Link`] entry from its raw URI text and parameter list (as produced by
+/// [`tokenize`]). When `base` is given, the target is resolved against it instead of being parsed
+/// as-is; `opts` controls whether query and parameter values are percent-decoded.
+fn build_link(
+    raw_uri: String,
+    param_list: Vec<(String, String)>,
+    base: Option<&str>,
+    opts: ParseOptions,
+) -> Result<Link> {
+    let uri: Uri = match base {
+        Some(base) => resolve_reference(&raw_uri, base)?,
+        None => raw_uri.parse().or(Err(Error(ErrorKind::InvalidURI)))?,
+    };
+
+    let mut queries = HashMap::new();
+    if let Some(query) = uri.query() {
+        let mut query = query.to_string();
+
+        // skip leading ampersand
+        if query.starts_with('&') {
+            query = query.chars().skip(1).collect();
+        }
+
+        // split each query and extract as (key, value) pairs
+        for q in query.split('&') {
+            let (key, val) = q.split_once('=').ok_or(Error(ErrorKind::MalformedQuery))?;
+
+            let val = if opts.decode_queries {
+                decode_percent_str(val, ErrorKind::MalformedQuery)?
+            } else {
+                val.to_string()
+            };
+
+            queries.insert(key.to_string(), val);
+        }
+    }
+
+    let mut params = HashMap::new();
+    let mut param_langs = HashMap::new();
+    let mut ext_params = Vec::new();
+
+    // extended (`name*`) params are decoded after plain ones, so that a `name*`
+    // alongside a plain `name` wins, matching RFC 8187's recommended handling
+    for (key, val) in param_list {
+        match key.strip_suffix('*') {
+            Some(base) => ext_params.push((base.to_string(), val)),
+            None => {
+                let val = if opts.decode_params {
+                    decode_percent_str(&val, ErrorKind::MalformedParam)?
+                } else {
+                    val
+                };
+
+                params.insert(key, val);
+            }
+        }
+    }
+
+    for (base, raw) in ext_params {
+        let (_charset, lang, value) = decode_ext_value(&raw)?;
+
+        if !lang.is_empty() {
+            param_langs.insert(base.clone(), lang);
+        }
+
+        params.insert(base, value);
+    }
+
+    Ok(Link {
+        uri,
+        raw_uri,
+        queries,
+        params,
+        param_langs,
+    })
+}
+
+/// Generic parser function
+///
+/// Does the actual parsing work, and then uses make_key() to proceses the HashMap key into the
+/// desired type. When `base` is given, each target is resolved against it instead of being
+/// parsed as-is, so relative-reference targets resolve to an absolute `uri`. `opts` controls
+/// whether query and parameter values are percent-decoded.
+///
+/// Entries sharing the same key (most often the `rel` parameter) overwrite one another; use
+/// [`parse_with_multi`] to keep every entry instead.
+fn parse_with<K, F>(
+    link_header: &str,
+    base: Option<&str>,
+    opts: ParseOptions,
+    make_key: F,
+) -> Result<HashMap<K, Link>>
+where
+    K: Eq + std::hash::Hash,
+    F: Fn(Option<String>) -> Result<K>,
+{
+    let mut result = HashMap::new();
+
+    for (raw_uri, param_list) in tokenize(link_header)? {
+        let link = build_link(raw_uri, param_list, base, opts)?;
+
+        result.insert(make_key(link.params.get("rel").cloned())?, link);
+    }
+
+    Ok(result)
+}
+
+/// Generic parser function that keeps every entry instead of letting later ones with the same
+/// key overwrite earlier ones, preserving header order within each key's `Vec`.
+fn parse_with_multi<K, F>(link_header: &str, make_key: F) -> Result<HashMap<K, Vec<Link>>>
+where
+    K: Eq + std::hash::Hash,
+    F: Fn(Option<String>) -> Result<K>,
+{
+    let mut result: HashMap<K, Vec<Link>> = HashMap::new();
+
+    for (raw_uri, param_list) in tokenize(link_header)? {
+        let link = build_link(raw_uri, param_list, None, ParseOptions::default())?;
+        let key = make_key(link.params.get("rel").cloned())?;
+
+        result.entry(key).or_default().push(link);
     }
 
     Ok(result)
 }
 
+/// Parse link header into a `HashMap<Option<Rel>, Vec<Link>>`, preserving every entry that shares
+/// a `rel` (e.g. two `rel="alternate"` entries with different `type`s) in header order, instead
+/// of letting all but the last overwrite each other as [`parse`] does.
+pub fn parse_multi(link_header: &str) -> Result<HashMap<Option<Rel>, Vec<Link>>> {
+    parse_with_multi(link_header, Ok)
+}
+
+/// Like [`parse_multi`], but requires every entry to carry a `rel` parameter, returning a
+/// `HashMap<Rel, Vec<Link>>` keyed directly on it -- the multi-value counterpart to
+/// [`parse_with_rel`].
+pub fn parse_with_rel_multi(link_header: &str) -> Result<HashMap<Rel, Vec<Link>>> {
+    parse_with_multi(link_header, |x| x.ok_or(Error(ErrorKind::MissingRel)))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -305,6 +920,7 @@ mod tests {
                     .iter()
                     .cloned()
                     .collect(),
+                param_langs: HashMap::new(),
             },
         );
         expected.insert(
@@ -323,6 +939,7 @@ mod tests {
                     .iter()
                     .cloned()
                     .collect(),
+                param_langs: HashMap::new(),
             },
         );
 
@@ -344,6 +961,7 @@ mod tests {
                         .iter()
                         .cloned()
                         .collect(),
+                    param_langs: HashMap::new(),
                 },
             );
 
@@ -374,6 +992,7 @@ mod tests {
                     .iter()
                     .cloned()
                     .collect(),
+                param_langs: HashMap::new(),
             },
         );
         expected.insert(
@@ -392,6 +1011,7 @@ mod tests {
                     .iter()
                     .cloned()
                     .collect(),
+                param_langs: HashMap::new(),
             },
         );
 
@@ -413,6 +1033,7 @@ mod tests {
                         .iter()
                         .cloned()
                         .collect(),
+                    param_langs: HashMap::new(),
                 },
             );
 
@@ -459,6 +1080,7 @@ mod tests {
                 .iter()
                 .cloned()
                 .collect(),
+                param_langs: HashMap::new(),
             },
         );
 
@@ -481,6 +1103,7 @@ mod tests {
                 .iter()
                 .cloned()
                 .collect(),
+                param_langs: HashMap::new(),
             },
         );
 
@@ -489,6 +1112,249 @@ mod tests {
         assert_eq!(expected, parsed);
     }
 
+    #[test]
+    fn parse_link_header_handles_quoted_commas_and_semicolons() {
+        let link_header = r#"<https://api.github.com/repositories/41986369/contributors?page=2>; rel="next"; title="Page 2, final; ish""#;
+
+        let parsed = parse(link_header).unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.params.get("title").unwrap(), "Page 2, final; ish");
+    }
+
+    #[test]
+    fn parse_link_header_handles_escaped_quotes() {
+        let link_header = r#"<https://api.github.com/repositories/41986369/contributors?page=2>; rel="next"; title="say \"hi\"""#;
+
+        let parsed = parse(link_header).unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.params.get("title").unwrap(), r#"say "hi""#);
+    }
+
+    #[test]
+    fn parse_link_header_decodes_rfc8187_extended_params() {
+        let link_header =
+            r#"<https://example.com/rates>; rel="next"; title*=UTF-8'en'%E2%82%AC%20rates"#;
+
+        let parsed = parse(link_header).unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.params.get("title").unwrap(), "\u{20ac} rates");
+        assert_eq!(link.param_langs.get("title").unwrap(), "en");
+    }
+
+    #[test]
+    fn parse_link_header_extended_param_overrides_plain_one() {
+        let link_header = r#"<https://example.com/rates>; rel="next"; title="fallback"; title*=UTF-8'en'%E2%82%AC%20rates"#;
+
+        let parsed = parse(link_header).unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.params.get("title").unwrap(), "\u{20ac} rates");
+    }
+
+    #[test]
+    fn parse_link_header_bad_ext_param_charset_should_err() {
+        assert_eq!(
+            parse(r#"<https://example.com/rates>; rel="next"; title*=ISO-8859-1'en'%A4"#),
+            Err(Error(ErrorKind::MalformedParam))
+        );
+    }
+
+    #[test]
+    fn format_link_header_round_trips() {
+        let link_header = r#"<https://api.github.com/repositories/41986369/contributors?page=2>; rel="next", <https://api.github.com/repositories/41986369/contributors?page=14>; rel="last""#;
+
+        let map = parse(link_header).unwrap();
+        let formatted = format_link_header(&map);
+        let reparsed = parse(&formatted).unwrap();
+
+        assert_eq!(map, reparsed);
+    }
+
+    #[test]
+    fn link_display_quotes_params_and_puts_rel_first() {
+        let link_header = r#"<https://example.com>; rel="next"; title="Page 2, final""#;
+
+        let link = &parse(link_header).unwrap()[&Some("next".to_string())];
+
+        assert_eq!(
+            link.to_string(),
+            r#"<https://example.com>; rel="next"; title="Page 2, final""#
+        );
+    }
+
+    #[test]
+    fn link_display_re_encodes_extended_params() {
+        let link_header = r#"<https://example.com/rates>; rel="next"; title*=UTF-8'en'%E2%82%AC%20rates"#;
+
+        let link = &parse(link_header).unwrap()[&Some("next".to_string())];
+
+        assert_eq!(link.to_string(), link_header);
+    }
+
+    #[test]
+    fn parse_with_base_resolves_relative_targets() {
+        let link_header = r#"</repositories/41986369/contributors?page=2>; rel="next""#;
+
+        let parsed = parse_with_base(link_header, "https://api.github.com/current").unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(
+            link.uri.to_string(),
+            "https://api.github.com/repositories/41986369/contributors?page=2"
+        );
+        assert_eq!(link.raw_uri, "/repositories/41986369/contributors?page=2");
+    }
+
+    #[test]
+    fn parse_with_base_resolves_dot_segments() {
+        let link_header = r#"<../sibling?x=1>; rel="next""#;
+
+        let parsed = parse_with_base(link_header, "https://example.com/a/b/current").unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.uri.to_string(), "https://example.com/a/sibling?x=1");
+    }
+
+    #[test]
+    fn parse_with_base_leaves_absolute_targets_untouched() {
+        let link_header = r#"<https://other.example/page>; rel="next""#;
+
+        let parsed = parse_with_base(link_header, "https://example.com/a/b/current").unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.uri.to_string(), "https://other.example/page");
+    }
+
+    #[test]
+    fn parse_keeps_raw_encoded_values_by_default() {
+        let link_header = r#"<https://example.com/search?q=rust%20lang>; rel="next"; title="Page%201""#;
+
+        let parsed = parse(link_header).unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.queries.get("q").unwrap(), "rust%20lang");
+        assert_eq!(link.params.get("title").unwrap(), "Page%201");
+    }
+
+    #[test]
+    fn parse_with_opts_decodes_queries_and_params() {
+        let link_header = r#"<https://example.com/search?q=rust%20lang>; rel="next"; title="Page%201""#;
+
+        let opts = ParseOptions {
+            decode_queries: true,
+            decode_params: true,
+        };
+        let parsed = parse_with_opts(link_header, &opts).unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.queries.get("q").unwrap(), "rust lang");
+        assert_eq!(link.params.get("title").unwrap(), "Page 1");
+        assert_eq!(link.raw_uri, "https://example.com/search?q=rust%20lang");
+    }
+
+    #[test]
+    fn parse_with_opts_reports_malformed_percent_sequences() {
+        let opts = ParseOptions {
+            decode_queries: true,
+            decode_params: false,
+        };
+
+        assert_eq!(
+            parse_with_opts(r#"<https://example.com/search?q=rust%2>; rel="next""#, &opts),
+            Err(Error(ErrorKind::MalformedQuery))
+        );
+    }
+
+    #[test]
+    fn parse_multi_preserves_entries_sharing_a_rel() {
+        let link_header = r#"<https://example.com/feed.json>; rel="alternate"; type="application/json", <https://example.com/feed.xml>; rel="alternate"; type="application/xml""#;
+
+        let parsed = parse_multi(link_header).unwrap();
+        let alternates = parsed.get(&Some("alternate".to_string())).unwrap();
+
+        assert_eq!(alternates.len(), 2);
+        assert_eq!(alternates[0].params.get("type").unwrap(), "application/json");
+        assert_eq!(alternates[1].params.get("type").unwrap(), "application/xml");
+    }
+
+    #[test]
+    fn parse_with_rel_multi_preserves_entries_sharing_a_rel() {
+        let link_header = r#"<https://example.com/feed.json>; rel="alternate"; type="application/json", <https://example.com/feed.xml>; rel="alternate"; type="application/xml""#;
+
+        let parsed = parse_with_rel_multi(link_header).unwrap();
+        let alternates = parsed.get("alternate").unwrap();
+
+        assert_eq!(alternates.len(), 2);
+    }
+
+    #[test]
+    fn parse_with_rel_multi_should_err_without_rel() {
+        assert_eq!(
+            parse_with_rel_multi(r#"<http://local.host/foo/bar>; type="foo/bar""#),
+            Err(Error(ErrorKind::MissingRel))
+        );
+    }
+
+    #[test]
+    fn parse_link_header_missing_closing_angle_bracket_should_err() {
+        assert_eq!(
+            parse("<https://example.com/no-closing-bracket"),
+            Err(Error(ErrorKind::MalformedParam))
+        );
+    }
+
+    #[test]
+    fn parse_link_header_unterminated_quote_should_err() {
+        assert_eq!(
+            parse(r#"<https://example.com>; rel="next"#),
+            Err(Error(ErrorKind::MalformedParam))
+        );
+    }
+
+    #[test]
+    fn parse_link_header_empty_value_does_not_swallow_next_entry() {
+        let link_header =
+            r#"<https://a.example>; rel=, <https://b.example>; rel="next""#;
+
+        let parsed = parse(link_header).unwrap();
+
+        assert_eq!(
+            parsed.get(&Some("".to_string())).unwrap().raw_uri,
+            "https://a.example"
+        );
+        assert_eq!(
+            parsed.get(&Some("next".to_string())).unwrap().raw_uri,
+            "https://b.example"
+        );
+    }
+
+    #[test]
+    fn parse_link_header_empty_value_before_semicolon() {
+        let parsed = parse(r#"<https://example.com>; rel=;foo=bar"#).unwrap();
+        let link = parsed.get(&Some("".to_string())).unwrap();
+
+        assert_eq!(link.params.get("foo").unwrap(), "bar");
+    }
+
+    #[test]
+    fn parse_link_header_dangling_key_at_eof_does_not_drop_param() {
+        let parsed = parse(r#"<https://example.com>; rel="next"; title="#).unwrap();
+        let link = parsed.get(&Some("next".to_string())).unwrap();
+
+        assert_eq!(link.params.get("title").unwrap(), "");
+    }
+
+    #[test]
+    fn parse_link_header_keyless_param_should_err() {
+        assert_eq!(
+            parse(r#"<https://a.example>; rel="next"; noval, <https://b.example>; rel="other""#),
+            Err(Error(ErrorKind::MalformedParam))
+        );
+    }
+
     #[test]
     fn test_error_display() {
         assert_eq!(